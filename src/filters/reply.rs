@@ -19,12 +19,15 @@
 //! Wrapping allows adding in conditional logic *before* the request enters
 //! the inner filter (though the `with::header` wrapper does not).
 
-use http::header::{HeaderName, HeaderValue};
+use std::error::Error as StdError;
+use std::fmt;
+
+use http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
 use http::HttpTryFrom;
 
 use ::filter::{Filter, Map, One, WrapSealed};
-use ::reply::Reply;
-use self::sealed::{WithHeader_, WithDefaultHeader_};
+use ::reply::{Reply, Response};
+use self::sealed::{WithHeader_, WithHeaders_, WithDefaultHeader_, WithRemoveHeader_, WithHeaderWith_};
 
 /// Wrap a [`Filter`](::Filter) that adds a header to the reply.
 ///
@@ -50,7 +53,87 @@ where
     }
 }
 
-// pub fn headers?
+/// Same as [`header`](header), but returns a `Result` instead of panicking
+/// when the name or value is invalid.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// let with_header = warp::reply::with::try_header("foo", "bar")
+///     .expect("foo: bar is a valid header");
+///
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(with_header);
+/// ```
+pub fn try_header<K, V>(name: K, value: V) -> Result<WithHeader, InvalidHeader>
+where
+    HeaderName: HttpTryFrom<K>,
+    HeaderValue: HttpTryFrom<V>,
+{
+    let (name, value) = try_name_and_value(name, value)?;
+    Ok(WithHeader {
+        name,
+        value,
+    })
+}
+
+/// Wrap a [`Filter`](::Filter) that adds a header to the reply, computed
+/// from the response produced by the inner filter.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+/// use warp::http::header::HeaderValue;
+///
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(warp::reply::with::header_with("x-powered-by", |resp: &warp::reply::Response| {
+///         if resp.status().is_success() {
+///             Some(HeaderValue::from_static("warp"))
+///         } else {
+///             None
+///         }
+///     }));
+/// ```
+pub fn header_with<K, F>(name: K, func: F) -> WithHeaderWith<F>
+where
+    HeaderName: HttpTryFrom<K>,
+    F: Fn(&Response) -> Option<HeaderValue>,
+{
+    let name = try_name(name)
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    WithHeaderWith {
+        name,
+        func,
+    }
+}
+
+/// Wrap a [`Filter`](::Filter) that adds multiple headers to the reply.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+/// use warp::http::header::{HeaderMap, HeaderValue};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("server", HeaderValue::from_static("warp"));
+/// headers.insert("content-type", HeaderValue::from_static("text/html"));
+///
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(warp::reply::with::headers(headers));
+/// ```
+pub fn headers(headers: HeaderMap) -> WithHeaders {
+    WithHeaders {
+        headers,
+    }
+}
 
 /// Wrap a [`Filter`](::Filter) that adds a header to the reply, if they
 /// aren't already set.
@@ -77,6 +160,83 @@ where
     }
 }
 
+/// Same as [`default_header`](default_header), but returns a `Result`
+/// instead of panicking when the name or value is invalid.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// let with_header = warp::reply::with::try_default_header("server", "warp")
+///     .expect("server: warp is a valid header");
+///
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(with_header);
+/// ```
+pub fn try_default_header<K, V>(name: K, value: V) -> Result<WithDefaultHeader, InvalidHeader>
+where
+    HeaderName: HttpTryFrom<K>,
+    HeaderValue: HttpTryFrom<V>,
+{
+    let (name, value) = try_name_and_value(name, value)?;
+    Ok(WithDefaultHeader {
+        name,
+        value,
+    })
+}
+
+/// Wrap a [`Filter`](::Filter) that adds a `content-type` header to the
+/// reply, if it isn't already set.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// // Set `content-type: application/json` if not already set.
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(warp::reply::with::default_content_type("application/json"));
+/// ```
+pub fn default_content_type<V>(value: V) -> WithDefaultHeader
+where
+    HeaderValue: HttpTryFrom<V>,
+{
+    let value = try_value(value)
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    WithDefaultHeader {
+        name: CONTENT_TYPE,
+        value,
+    }
+}
+
+/// Wrap a [`Filter`](::Filter) that removes a header from the reply.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// // Remove the `server` header, however it may have been set.
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(warp::reply::with::remove("server"));
+/// ```
+pub fn remove<K>(name: K) -> WithRemoveHeader
+where
+    HeaderName: HttpTryFrom<K>,
+{
+    let name = try_name(name)
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    WithRemoveHeader {
+        name,
+    }
+}
+
 /// Wrap a `Filter` to always set a header.
 #[derive(Clone, Debug)]
 pub struct WithHeader {
@@ -112,6 +272,76 @@ where
 }
 
 
+/// Wrap a `Filter` to set a header, computed from the produced response.
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct WithHeaderWith<F> {
+    name: HeaderName,
+    func: F,
+}
+
+impl<F> WithHeaderWith<F> {
+    #[doc(hidden)]
+    #[deprecated(note="use Filter::with(decorator) instead")]
+    pub fn decorate<FT, R>(&self, inner: FT) -> Map<FT, WithHeaderWith_<F>>
+    where
+        FT: Filter<Extract=One<R>>,
+        F: Fn(&Response) -> Option<HeaderValue> + Clone,
+        R: Reply,
+    {
+        inner.with(self)
+    }
+}
+
+impl<FT, F, R> WrapSealed<FT> for WithHeaderWith<F>
+where
+    FT: Filter<Extract=One<R>>,
+    F: Fn(&Response) -> Option<HeaderValue> + Clone,
+    R: Reply,
+{
+    type Wrapped = Map<FT, WithHeaderWith_<F>>;
+
+    fn wrap(&self, filter: FT) -> Self::Wrapped {
+        let with = WithHeaderWith_ {
+            with: self.clone(),
+        };
+        filter.map(with)
+    }
+}
+
+/// Wrap a `Filter` to always set multiple headers.
+#[derive(Clone, Debug)]
+pub struct WithHeaders {
+    headers: HeaderMap,
+}
+
+impl WithHeaders {
+    #[doc(hidden)]
+    #[deprecated(note="use Filter::with(decorator) instead")]
+    pub fn decorate<F, R>(&self, inner: F) -> Map<F, WithHeaders_>
+    where
+        F: Filter<Extract=One<R>>,
+        R: Reply,
+    {
+        inner.with(self)
+    }
+}
+
+impl<F, R> WrapSealed<F> for WithHeaders
+where
+    F: Filter<Extract=One<R>>,
+    R: Reply,
+{
+    type Wrapped = Map<F, WithHeaders_>;
+
+    fn wrap(&self, filter: F) -> Self::Wrapped {
+        let with = WithHeaders_ {
+            with: self.clone(),
+        };
+        filter.map(with)
+    }
+}
+
 /// Wrap a `Filter` to set a header if it is not already set.
 #[derive(Clone, Debug)]
 pub struct WithDefaultHeader {
@@ -146,26 +376,97 @@ where
     }
 }
 
+/// Wrap a `Filter` to remove a header.
+#[derive(Clone, Debug)]
+pub struct WithRemoveHeader {
+    name: HeaderName,
+}
+
+impl WithRemoveHeader {
+    #[doc(hidden)]
+    #[deprecated(note="use Filter::with(decorator) instead")]
+    pub fn decorate<F, R>(&self, inner: F) -> Map<F, WithRemoveHeader_>
+    where
+        F: Filter<Extract=One<R>>,
+        R: Reply,
+    {
+        inner.with(self)
+    }
+}
+
+impl<F, R> WrapSealed<F> for WithRemoveHeader
+where
+    F: Filter<Extract=One<R>>,
+    R: Reply,
+{
+    type Wrapped = Map<F, WithRemoveHeader_>;
+
+    fn wrap(&self, filter: F) -> Self::Wrapped {
+        let with = WithRemoveHeader_ {
+            with: self.clone(),
+        };
+        filter.map(with)
+    }
+}
+
 fn assert_name_and_value<K, V>(name: K, value: V) -> (HeaderName, HeaderValue)
 where
     HeaderName: HttpTryFrom<K>,
     HeaderValue: HttpTryFrom<V>,
 {
-    let name = <HeaderName as HttpTryFrom<K>>::try_from(name)
+    try_name_and_value(name, value)
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+fn try_name<K>(name: K) -> Result<HeaderName, InvalidHeader>
+where
+    HeaderName: HttpTryFrom<K>,
+{
+    <HeaderName as HttpTryFrom<K>>::try_from(name)
         .map_err(Into::into)
-        .unwrap_or_else(|_| panic!("invalid header name"));
+        .map_err(InvalidHeader)
+}
 
-    let value = <HeaderValue as HttpTryFrom<V>>::try_from(value)
+fn try_value<V>(value: V) -> Result<HeaderValue, InvalidHeader>
+where
+    HeaderValue: HttpTryFrom<V>,
+{
+    <HeaderValue as HttpTryFrom<V>>::try_from(value)
         .map_err(Into::into)
-        .unwrap_or_else(|_| panic!("invalid header value"));
+        .map_err(InvalidHeader)
+}
 
-    (name, value)
+fn try_name_and_value<K, V>(name: K, value: V) -> Result<(HeaderName, HeaderValue), InvalidHeader>
+where
+    HeaderName: HttpTryFrom<K>,
+    HeaderValue: HttpTryFrom<V>,
+{
+    Ok((try_name(name)?, try_value(value)?))
+}
+
+/// An error returned when attempting to build a header wrapper with an
+/// invalid header name or value.
+#[derive(Debug)]
+pub struct InvalidHeader(http::Error);
+
+impl fmt::Display for InvalidHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid header: {}", self.0)
+    }
+}
+
+impl StdError for InvalidHeader {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
 }
 
 mod sealed {
+    use http::header::HeaderValue;
+
     use ::generic::{Func, One};
-    use ::reply::{Reply, Reply_};
-    use super::{WithHeader, WithDefaultHeader};
+    use ::reply::{Reply, Reply_, Response};
+    use super::{WithHeader, WithHeaderWith, WithHeaders, WithDefaultHeader, WithRemoveHeader};
 
     #[derive(Clone)]
     #[allow(missing_debug_implementations)]
@@ -184,6 +485,47 @@ mod sealed {
         }
     }
 
+    #[derive(Clone)]
+    #[allow(missing_debug_implementations)]
+    pub struct WithHeaderWith_<F> {
+        pub(super) with: WithHeaderWith<F>,
+    }
+
+    impl<F, R> Func<One<R>> for WithHeaderWith_<F>
+    where
+        F: Fn(&Response) -> Option<HeaderValue>,
+        R: Reply,
+    {
+        type Output = Reply_;
+
+        fn call(&self, args: One<R>) -> Self::Output {
+            let mut resp = args.0.into_response();
+            if let Some(value) = (self.with.func)(&resp) {
+                resp.headers_mut().insert(&self.with.name, value);
+            }
+            Reply_(resp)
+        }
+    }
+
+    #[derive(Clone)]
+    #[allow(missing_debug_implementations)]
+    pub struct WithHeaders_ {
+        pub(super) with: WithHeaders,
+    }
+
+    impl<R: Reply> Func<One<R>> for WithHeaders_ {
+        type Output = Reply_;
+
+        fn call(&self, args: One<R>) -> Self::Output {
+            let mut resp = args.0.into_response();
+            // `extend` replaces any header names already set, but still
+            // appends (rather than overwriting) when `self.with.headers`
+            // itself carries multiple values for the same name.
+            resp.headers_mut().extend(self.with.headers.clone());
+            Reply_(resp)
+        }
+    }
+
     #[derive(Clone)]
     #[allow(missing_debug_implementations)]
     pub struct WithDefaultHeader_ {
@@ -204,4 +546,20 @@ mod sealed {
             Reply_(resp)
         }
     }
+
+    #[derive(Clone)]
+    #[allow(missing_debug_implementations)]
+    pub struct WithRemoveHeader_ {
+        pub(super) with: WithRemoveHeader,
+    }
+
+    impl<R: Reply> Func<One<R>> for WithRemoveHeader_ {
+        type Output = Reply_;
+
+        fn call(&self, args: One<R>) -> Self::Output {
+            let mut resp = args.0.into_response();
+            resp.headers_mut().remove(&self.with.name);
+            Reply_(resp)
+        }
+    }
 }
@@ -0,0 +1,133 @@
+extern crate warp;
+
+use warp::Filter;
+use warp::http::header::{HeaderMap, HeaderValue};
+
+#[test]
+fn headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("warp"));
+    headers.insert("content-type", HeaderValue::from_static("text/html"));
+
+    let reply = warp::any()
+        .map(warp::reply)
+        .with(warp::reply::with::headers(headers));
+
+    let res = warp::test::request().reply(&reply);
+
+    assert_eq!(res.headers()["server"], "warp");
+    assert_eq!(res.headers()["content-type"], "text/html");
+}
+
+#[test]
+fn headers_keeps_duplicate_names() {
+    let mut headers = HeaderMap::new();
+    headers.append("x-multi", HeaderValue::from_static("a"));
+    headers.append("x-multi", HeaderValue::from_static("b"));
+
+    let reply = warp::any()
+        .map(warp::reply)
+        .with(warp::reply::with::headers(headers));
+
+    let res = warp::test::request().reply(&reply);
+
+    let values: Vec<_> = res.headers().get_all("x-multi").iter().collect();
+    assert_eq!(values, vec!["a", "b"]);
+}
+
+#[test]
+fn default_content_type_fills_missing() {
+    let reply = warp::any()
+        .map(warp::reply)
+        .with(warp::reply::with::default_content_type("application/json"));
+
+    let res = warp::test::request().reply(&reply);
+
+    assert_eq!(res.headers()["content-type"], "application/json");
+}
+
+#[test]
+fn default_content_type_does_not_clobber() {
+    let reply = warp::any()
+        .map(|| warp::reply::html("<h1>warp</h1>"))
+        .with(warp::reply::with::default_content_type("application/json"));
+
+    let res = warp::test::request().reply(&reply);
+
+    assert_eq!(res.headers()["content-type"], "text/html; charset=utf-8");
+}
+
+#[test]
+fn remove() {
+    let reply = warp::any()
+        .map(|| warp::reply::with_header(warp::reply(), "server", "warp"))
+        .with(warp::reply::with::remove("server"));
+
+    let res = warp::test::request().reply(&reply);
+
+    assert!(!res.headers().contains_key("server"));
+}
+
+#[test]
+fn header_with_some() {
+    let header = warp::reply::with::header_with("x-success", |resp: &warp::reply::Response| {
+        if resp.status().is_success() {
+            Some(HeaderValue::from_static("yes"))
+        } else {
+            None
+        }
+    });
+
+    let reply = warp::any().map(warp::reply).with(header);
+
+    let res = warp::test::request().reply(&reply);
+
+    assert_eq!(res.headers()["x-success"], "yes");
+}
+
+#[test]
+fn header_with_none_leaves_unset() {
+    let header = warp::reply::with::header_with("x-never", |_: &warp::reply::Response| None);
+
+    let reply = warp::any().map(warp::reply).with(header);
+
+    let res = warp::test::request().reply(&reply);
+
+    assert!(!res.headers().contains_key("x-never"));
+}
+
+#[test]
+fn try_header_ok() {
+    let header = warp::reply::with::try_header("foo", "bar")
+        .expect("foo: bar is a valid header");
+
+    let reply = warp::any().map(warp::reply).with(header);
+
+    let res = warp::test::request().reply(&reply);
+
+    assert_eq!(res.headers()["foo"], "bar");
+}
+
+#[test]
+fn try_header_err() {
+    warp::reply::with::try_header("invalid name", "bar")
+        .expect_err("header names may not contain spaces");
+}
+
+#[test]
+fn try_default_header_ok() {
+    let header = warp::reply::with::try_default_header("foo", "bar")
+        .expect("foo: bar is a valid header");
+
+    let reply = warp::any().map(warp::reply).with(header);
+
+    let res = warp::test::request().reply(&reply);
+
+    assert_eq!(res.headers()["foo"], "bar");
+}
+
+#[test]
+fn try_default_header_err() {
+    warp::reply::with::try_default_header("foo", "bar\n")
+        .expect_err("header values may not contain newlines");
+}